@@ -1,59 +1,78 @@
-use rustgrad::{engine::Val, loss, nn::MLP};
+use rustgrad::{
+    nn::MLP,
+    optim::{Optimizer, Sgd},
+    tensor::Tensor,
+};
 
 const ALPHA: f32 = 0.035;
 
 fn main() {
     // Input layer: 3 inputs
     // 2 hidden layers of 5
-    // A single output layer
-    let mlp = MLP::new(&[3, 5, 5, 1]);
+    // One output per class: [cannot go, go]
+    let mlp = MLP::new(&[3, 5, 5, 2]);
     gradient_descent(&mlp, 100);
 }
 
-/// The input is a list of sets of traffic lights ordered Red Yellow Green
+/// The input is a batch of sets of traffic lights ordered Red Yellow Green
 /// The numeric values of the lights indicates the brightness
 ///
-/// The model should make a prediction on whether or not it can go based on an
-/// input of a set of lights
-///
-/// 0.0 -> 1.0 ranges from definitely not moving to definitely should move
+/// The model should classify whether it can go based on an input of a set of
+/// lights, trained with a softmax + cross-entropy loss over the two classes
+/// rather than treating "go" as a single continuous 0.0 -> 1.0 target.
 fn gradient_descent(mlp: &MLP, epochs: usize) {
-    let xs = vec![
-        // [Red, Yellow, Green] lights
-        // 0 = Completely dark
-        // 1 = Fully bright
-        vec![Val::new(1.0), Val::new(0.0), Val::new(0.0)],
-        vec![Val::new(0.25), Val::new(1.0), Val::new(0.1)],
-        vec![Val::new(0.1), Val::new(0.25), Val::new(0.95)],
-        vec![Val::new(0.1), Val::new(0.3), Val::new(0.85)],
-    ];
-    // 1 = Go
-    // 0 = Cannot go
-    let ys = vec![Val::new(0.0), Val::new(0.0), Val::new(1.0), Val::new(1.0)];
+    // [Red, Yellow, Green] lights, one row per example
+    // 0 = Completely dark
+    // 1 = Fully bright
+    let xs = Tensor::new(
+        vec![
+            1.0, 0.0, 0.0, //
+            0.25, 1.0, 0.1, //
+            0.1, 0.25, 0.95, //
+            0.1, 0.3, 0.85,
+        ],
+        (4, 3),
+    );
+    // One-hot [cannot go, go] per example
+    let ys = Tensor::new(
+        vec![
+            1.0, 0.0, //
+            1.0, 0.0, //
+            0.0, 1.0, //
+            0.0, 1.0,
+        ],
+        (4, 2),
+    );
+
+    // Build the expression graph once. Weights only ever change in place via
+    // `add_data`, so each epoch just resets and recomputes this same graph
+    // instead of reallocating it from scratch.
+    let logits = mlp.call(&xs);
+    let log_probs = logits.log_softmax();
+    let loss = log_probs.cross_entropy(&ys);
+
+    // Swapping this for `Adam::new(...)` (and importing `Adam` above) is all a
+    // training loop needs to change to pick a different update rule.
+    let mut optimizer = Sgd::new(ALPHA, 0.0);
+
     for i in 0..epochs {
         println!("Iteration: {}", i + 1);
-        // Forward pass
-        let ypred: Vec<Val> = xs.iter().map(|input| mlp.call(input)).flatten().collect();
-        println!(
-            "Pred: {:?}\nActual: {:?}",
-            ypred.iter().map(|v| v.data()).collect::<Vec<_>>(),
-            ys.iter().map(|v| v.data()).collect::<Vec<_>>(),
-        );
 
-        // Loss function
-        let loss = loss(&ypred, &ys);
-        println!("Loss: {}", loss.data());
+        // Forward pass
+        loss.reset_computation();
+        loss.forward();
+        let probs: Vec<f32> = log_probs.data().iter().map(|p| p.exp()).collect();
+        println!("P([cannot go, go]): {:?}\nActual: {:?}", probs, ys.data());
+        println!("Loss: {}", loss.data()[0]);
 
         // Resetting the gradients
-        mlp.zero_grad();
+        optimizer.zero_grad(&mlp.parameters());
 
         // Calculating the gradients
         loss.backward();
 
         // Updating the weights
-        mlp.parameters().iter_mut().for_each(|p| {
-            p.add_data(-ALPHA * p.grad());
-        });
+        optimizer.step(&mlp.parameters());
         println!();
     }
 }
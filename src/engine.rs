@@ -51,13 +51,39 @@ impl Val {
         }
     }
 
+    pub fn ln(&self) -> Self {
+        Self {
+            inner: ValInner::ln(self.inner.clone()),
+        }
+    }
+
+    pub fn sigmoid(&self) -> Self {
+        Self {
+            inner: ValInner::sigmoid(self.inner.clone()),
+        }
+    }
+
     pub fn backward(&self) {
-        self.inner.as_ref().borrow_mut().backward();
+        ValInner::backward(&self.inner);
     }
 
     pub fn zero_grad(&self) {
         self.inner.as_ref().borrow_mut().grad = 0.0;
     }
+
+    /// Recomputes `data` for this node and everything it depends on, walking
+    /// up from the leaves. Nodes already marked as computed are skipped, so
+    /// call [`Val::reset_computation`] first if the leaves have changed.
+    pub fn forward(&self) {
+        ValInner::forward(&self.inner);
+    }
+
+    /// Clears the `computed` flag on this node and every node it depends on,
+    /// so the next [`Val::forward`] actually recomputes them instead of
+    /// reusing stale data.
+    pub fn reset_computation(&self) {
+        ValInner::reset_computation(&self.inner);
+    }
 }
 
 impl<T> From<T> for Val
@@ -331,6 +357,106 @@ mod tests {
         assert_eq!(1.0, c.grad());
     }
 
+    #[test]
+    fn test_forward_recomputes_after_leaf_mutation() {
+        let a = Val::new(2.0);
+        let b = Val::new(3.0);
+        let c = &a * &b;
+        let d = &c + &a;
+
+        assert_eq!(8.0, d.data());
+
+        a.add_data(1.0);
+        d.reset_computation();
+        d.forward();
+
+        assert_eq!(12.0, d.data());
+    }
+
+    #[test]
+    fn test_forward_is_a_noop_without_reset() {
+        let a = Val::new(2.0);
+        let b = &a * 3.0;
+
+        assert_eq!(6.0, b.data());
+
+        a.add_data(10.0);
+        // No reset_computation(), so forward should not touch the stale data.
+        b.forward();
+
+        assert_eq!(6.0, b.data());
+    }
+
+    #[test]
+    fn test_relu_backward_gradient_is_one_not_the_output_value() {
+        // At a positive input the ReLU derivative is 1 regardless of how
+        // large the activation itself is, not the activation's value.
+        let a = Val::new(5.0);
+        let b = a.relu();
+
+        b.backward();
+
+        assert_eq!(5.0, b.data());
+        assert_eq!(1.0, a.grad());
+    }
+
+    #[test]
+    fn test_ln_backward() {
+        let a = Val::new(2.0);
+        let b = a.ln();
+
+        b.backward();
+
+        assert!((b.data() - 2.0f32.ln()).abs() < 1e-6);
+        assert!((a.grad() - 0.5).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_sigmoid_backward() {
+        let a = Val::new(0.0);
+        let b = a.sigmoid();
+
+        b.backward();
+
+        assert_eq!(0.5, b.data());
+        assert_eq!(0.25, a.grad());
+    }
+
+    #[test]
+    fn test_diamond_shaped_graph() {
+        // a feeds two separate consumers (b and c) which are then summed, so
+        // a's gradient must be the sum of both paths rather than whichever
+        // path happens to reach it first.
+        let a = Val::new(3.0);
+        let b = &a * 2.0;
+        let c = &a * 3.0;
+        let d = &b + &c;
+
+        d.backward();
+
+        assert_eq!(15.0, d.data());
+        assert_eq!(1.0, d.grad());
+        assert_eq!(5.0, a.grad());
+    }
+
+    #[test]
+    fn test_wide_diamond_shaped_graph() {
+        // a wider fan-out: a feeds four consumers that all recombine into one
+        // root, which used to cause the naive recursive pass to re-descend
+        // the shared sub-DAG once per incoming path.
+        let a = Val::new(2.0);
+        let branches: Vec<Val> = (1..=4).map(|i| &a * i as f32).collect();
+        let root = branches
+            .into_iter()
+            .reduce(|acc, v| acc + v)
+            .unwrap();
+
+        root.backward();
+
+        assert_eq!(20.0, root.data());
+        assert_eq!(10.0, a.grad());
+    }
+
     #[test]
     fn test_micrograd_example() {
         let a = Val::new(-4.0);
@@ -1,5 +1,6 @@
 use std::{
-    cell::RefCell,
+    cell::{Cell, RefCell},
+    collections::HashSet,
     fmt::Display,
     rc::Rc,
 };
@@ -11,6 +12,7 @@ pub struct ValInner {
     pub data: f32,
     pub grad: f32,
     pub op: Option<Op>,
+    computed: Cell<bool>,
 }
 
 impl ValInner {
@@ -22,6 +24,9 @@ impl ValInner {
             data: data.into(),
             grad: 0.0,
             op: None,
+            // A node's `data` is always up to date with its inputs the
+            // moment it's constructed, so it starts out already computed.
+            computed: Cell::new(true),
         }
     }
 
@@ -83,14 +88,97 @@ impl ValInner {
         Self::rc(exp, Some(Op::Exp { val: value, exp }))
     }
 
-    pub fn backward(&mut self) {
-        self.grad = 1.0;
-        self.backward_inner();
+    pub fn ln(value: Rc<RefCell<Self>>) -> Rc<RefCell<Self>> {
+        let ln = value.as_ref().borrow().data.ln();
+        Self::rc(ln, Some(Op::Ln { val: value }))
     }
 
-    fn backward_inner(&mut self) {
-        if let Some(op) = &mut self.op {
-            op.backward(self.grad);
+    pub fn sigmoid(value: Rc<RefCell<Self>>) -> Rc<RefCell<Self>> {
+        let sigmoid = 1.0 / (1.0 + (-value.as_ref().borrow().data).exp());
+        Self::rc(sigmoid, Some(Op::Sigmoid { val: value, sigmoid }))
+    }
+
+    /// Runs a full reverse-mode pass starting at `root`.
+    ///
+    /// Builds a post-order (child-before-parent) traversal of the graph once,
+    /// visiting every node exactly once regardless of how many consumers it
+    /// fans out to, then walks that order in reverse so a node's gradient is
+    /// fully accumulated from every parent before it propagates further back.
+    pub fn backward(root: &Rc<RefCell<Self>>) {
+        let mut visited = HashSet::new();
+        let mut topo = Vec::new();
+        Self::build_topo(root, &mut visited, &mut topo);
+
+        root.as_ref().borrow_mut().grad = 1.0;
+
+        for node in topo.into_iter().rev() {
+            let mut value = node.as_ref().borrow_mut();
+            let grad = value.grad;
+            if let Some(op) = value.op.as_mut() {
+                op.backward(grad);
+            }
+        }
+    }
+
+    fn build_topo(
+        node: &Rc<RefCell<Self>>,
+        visited: &mut HashSet<usize>,
+        topo: &mut Vec<Rc<RefCell<Self>>>,
+    ) {
+        if !visited.insert(Rc::as_ptr(node) as *const _ as usize) {
+            return;
+        }
+        if let Some(op) = &node.as_ref().borrow().op {
+            for child in op.children() {
+                Self::build_topo(&child, visited, topo);
+            }
+        }
+        topo.push(Rc::clone(node));
+    }
+
+    /// Recomputes `data` for `node` from its `Op` inputs, bottom-up, reusing
+    /// already-computed nodes so a graph shared across many consumers is
+    /// only evaluated once per `forward` call.
+    pub fn forward(node: &Rc<RefCell<Self>>) {
+        if node.as_ref().borrow().computed.get() {
+            return;
+        }
+
+        let children = node
+            .as_ref()
+            .borrow()
+            .op
+            .as_ref()
+            .map(Op::children)
+            .unwrap_or_default();
+        for child in &children {
+            Self::forward(child);
+        }
+
+        let mut value = node.as_ref().borrow_mut();
+        if let Some(op) = value.op.as_mut() {
+            value.data = op.forward();
+        }
+        value.computed.set(true);
+    }
+
+    /// Marks `node` and everything it depends on as not-yet-computed and
+    /// zeroes its accumulated `grad`, so the next `forward()` actually
+    /// re-evaluates every node instead of reusing stale data, and the next
+    /// `backward()` doesn't keep piling gradient from previous epochs onto
+    /// intermediate nodes that nothing else zeroes.
+    pub fn reset_computation(node: &Rc<RefCell<Self>>) {
+        let mut value = node.as_ref().borrow_mut();
+        if !value.computed.get() {
+            return;
+        }
+        value.computed.set(false);
+        value.grad = 0.0;
+
+        let children = value.op.as_ref().map(Op::children).unwrap_or_default();
+        drop(value);
+        for child in &children {
+            Self::reset_computation(child);
         }
     }
 }
@@ -121,64 +209,109 @@ pub enum Op {
         relu: f32,
         prev: Rc<RefCell<ValInner>>,
     },
+    Ln {
+        val: Rc<RefCell<ValInner>>,
+    },
+    Sigmoid {
+        val: Rc<RefCell<ValInner>>,
+        sigmoid: f32,
+    },
 }
 
 impl Op {
+    /// Adds this op's contribution of `grad` into its immediate children only.
+    ///
+    /// Does not recurse: callers are expected to visit nodes in reverse
+    /// topological order so that every parent has already deposited its
+    /// gradient before a node's own `backward` runs.
     pub fn backward(&mut self, grad: f32) {
         match self {
             Op::Add { left, right } => {
                 if Rc::ptr_eq(left, right) {
-                    let mut value = left.as_ref().borrow_mut();
-                    value.grad += grad;
-                    value.grad += grad;
-                    value.backward_inner();
-                    value.backward_inner();
+                    left.as_ref().borrow_mut().grad += 2.0 * grad;
                 } else {
-                    let mut left_ref = left.as_ref().borrow_mut();
-                    let mut right_ref = right.as_ref().borrow_mut();
-                    left_ref.grad += grad;
-                    right_ref.grad += grad;
-                    left_ref.backward_inner();
-                    right_ref.backward_inner();
-                };
+                    left.as_ref().borrow_mut().grad += grad;
+                    right.as_ref().borrow_mut().grad += grad;
+                }
             }
             Op::Mul { left, right } => {
                 if Rc::ptr_eq(left, right) {
-                    let mut value = left.as_ref().borrow_mut();
-                    value.grad += 2.0 * value.data * grad;
-                    value.backward_inner();
-                    value.backward_inner();
+                    let data = left.as_ref().borrow().data;
+                    left.as_ref().borrow_mut().grad += 2.0 * data * grad;
                 } else {
-                    let mut left_ref = left.as_ref().borrow_mut();
-                    let mut right_ref = right.as_ref().borrow_mut();
-                    left_ref.grad += right_ref.data * grad;
-                    right_ref.grad += left_ref.data * grad;
-                    left_ref.backward_inner();
-                    right_ref.backward_inner();
+                    let left_data = left.as_ref().borrow().data;
+                    let right_data = right.as_ref().borrow().data;
+                    left.as_ref().borrow_mut().grad += right_data * grad;
+                    right.as_ref().borrow_mut().grad += left_data * grad;
                 }
             }
             Op::Tanh { val, tanh } => {
-                let mut value = val.as_ref().borrow_mut();
-                value.grad += (1.0 - tanh.powi(2)) * grad;
-                value.backward_inner();
+                val.as_ref().borrow_mut().grad += (1.0 - tanh.powi(2)) * grad;
             }
             Op::Exp { val, exp } => {
-                let mut value = val.as_ref().borrow_mut();
-                value.grad += *exp * grad;
-                value.backward_inner();
+                val.as_ref().borrow_mut().grad += *exp * grad;
             }
             Op::Pow { base, exponent } => {
-                let mut value = base.as_ref().borrow_mut();
-                value.grad += *exponent * (value.data.pow(*exponent - 1.0)) * grad;
-                value.backward_inner();
+                let data = base.as_ref().borrow().data;
+                base.as_ref().borrow_mut().grad += *exponent * (data.pow(*exponent - 1.0)) * grad;
             }
-            Op::ReLU { relu, prev } => {
+            Op::ReLU { relu: _, prev } => {
                 let mut value = prev.as_ref().borrow_mut();
-                value.grad += *relu * grad;
-                value.backward_inner();
+                let derivative = if value.data > 0.0 { 1.0 } else { 0.0 };
+                value.grad += derivative * grad;
+            }
+            Op::Ln { val } => {
+                let data = val.as_ref().borrow().data;
+                val.as_ref().borrow_mut().grad += (1.0 / data) * grad;
+            }
+            Op::Sigmoid { val, sigmoid } => {
+                val.as_ref().borrow_mut().grad += *sigmoid * (1.0 - *sigmoid) * grad;
             }
         }
     }
+
+    /// Recomputes this op's `data` from its children's current `data`,
+    /// refreshing any cached derivative terms (`tanh`, `exp`, `relu`) along
+    /// the way so a later `backward` sees values consistent with the latest
+    /// `forward`.
+    fn forward(&mut self) -> f32 {
+        match self {
+            Op::Add { left, right } => left.as_ref().borrow().data + right.as_ref().borrow().data,
+            Op::Mul { left, right } => left.as_ref().borrow().data * right.as_ref().borrow().data,
+            Op::Tanh { val, tanh } => {
+                *tanh = val.as_ref().borrow().data.tanh();
+                *tanh
+            }
+            Op::Exp { val, exp } => {
+                *exp = val.as_ref().borrow().data.exp();
+                *exp
+            }
+            Op::Pow { base, exponent } => base.as_ref().borrow().data.pow(*exponent),
+            Op::ReLU { relu, prev } => {
+                let data = prev.as_ref().borrow().data;
+                *relu = if data < 0.0 { 0.0 } else { data };
+                *relu
+            }
+            Op::Ln { val } => val.as_ref().borrow().data.ln(),
+            Op::Sigmoid { val, sigmoid } => {
+                *sigmoid = 1.0 / (1.0 + (-val.as_ref().borrow().data).exp());
+                *sigmoid
+            }
+        }
+    }
+
+    fn children(&self) -> Vec<Rc<RefCell<ValInner>>> {
+        match self {
+            Op::Add { left, right } => vec![Rc::clone(left), Rc::clone(right)],
+            Op::Mul { left, right } => vec![Rc::clone(left), Rc::clone(right)],
+            Op::Tanh { val, .. } => vec![Rc::clone(val)],
+            Op::Exp { val, .. } => vec![Rc::clone(val)],
+            Op::Pow { base, .. } => vec![Rc::clone(base)],
+            Op::ReLU { prev, .. } => vec![Rc::clone(prev)],
+            Op::Ln { val } => vec![Rc::clone(val)],
+            Op::Sigmoid { val, .. } => vec![Rc::clone(val)],
+        }
+    }
 }
 
 impl From<f32> for ValInner {
@@ -214,6 +347,10 @@ impl Display for Op {
             Op::Tanh { val, tanh: _ } => write!(f, "tanh({})", val.as_ref().borrow().data),
             Op::Exp { val, exp: _ } => write!(f, "e ** {}", val.as_ref().borrow().data),
             Op::ReLU { relu, prev: _ } => write!(f, "{}", relu),
+            Op::Ln { val } => write!(f, "ln({})", val.as_ref().borrow().data),
+            Op::Sigmoid { val, sigmoid: _ } => {
+                write!(f, "sigmoid({})", val.as_ref().borrow().data)
+            }
         }
     }
 }
@@ -3,6 +3,8 @@ use num_traits::pow::Pow;
 
 pub mod engine;
 pub mod nn;
+pub mod optim;
+pub mod tensor;
 
 pub fn loss(pred: &[Val], actual: &[Val]) -> Val {
     pred.iter()
@@ -11,3 +13,58 @@ pub fn loss(pred: &[Val], actual: &[Val]) -> Val {
         .reduce(|acc, curr| acc + curr)
         .unwrap()
 }
+
+/// Numerically stable log-softmax: subtracts the max logit (a plain `f32`,
+/// not part of the graph) before exponentiating, so `cross_entropy` can
+/// consume log-probabilities without `exp`/`ln` overflowing.
+pub fn log_softmax(logits: &[Val]) -> Vec<Val> {
+    let max = logits
+        .iter()
+        .map(|v| v.data())
+        .fold(f32::NEG_INFINITY, f32::max);
+    let shifted: Vec<Val> = logits.iter().map(|v| v - max).collect();
+    let log_sum_exp = shifted
+        .iter()
+        .map(|v| v.exp())
+        .reduce(|acc, curr| acc + curr)
+        .unwrap()
+        .ln();
+    shifted
+        .into_iter()
+        .map(|v| v - log_sum_exp.clone())
+        .collect()
+}
+
+/// Cross-entropy loss given log-probabilities (e.g. from [`log_softmax`])
+/// and a one-hot `target`.
+pub fn cross_entropy(log_probs: &[Val], target: &[Val]) -> Val {
+    let sum = log_probs
+        .iter()
+        .zip(target)
+        .map(|(p, t)| p * t)
+        .reduce(|acc, curr| acc + curr)
+        .unwrap();
+    -sum
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_log_softmax_sums_to_one() {
+        let logits = vec![Val::new(1.0), Val::new(2.0), Val::new(3.0)];
+        let log_probs = log_softmax(&logits);
+        let sum: f32 = log_probs.iter().map(|v| v.data().exp()).sum();
+        assert!((sum - 1.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_cross_entropy_picks_out_target_class() {
+        let logits = vec![Val::new(2.0), Val::new(0.5), Val::new(0.1)];
+        let target = vec![Val::new(1.0), Val::new(0.0), Val::new(0.0)];
+        let log_probs = log_softmax(&logits);
+        let loss = cross_entropy(&log_probs, &target);
+        assert_eq!(-log_probs[0].data(), loss.data());
+    }
+}
@@ -1,17 +1,18 @@
 use rand::Rng;
 
-use crate::engine::Val;
+use crate::tensor::Tensor;
 
-/// Individual neurons that will take in several [`Val`]s as input and do elementwise
-/// multiplication with them and it's own weights
-pub struct Neuron {
-    bias: Val,
-    weights: Vec<Val>,
-}
+mod io;
+
+pub use io::MlpWeights;
 
-/// A layer of neurons. The [`MLP`] is constructed with several of these of varying sizes
+/// A single layer of an [`MLP`]: one `(nin, nout)` weight matrix and one
+/// `(1, nout)` bias row applied to a batch of inputs via [`Tensor::matmul`],
+/// collapsing what used to be one scalar [`crate::engine::Val`] graph per
+/// neuron into a handful of tensor nodes.
 pub struct Layer {
-    neurons: Vec<Neuron>,
+    weights: Tensor,
+    bias: Tensor,
 }
 
 /// Multi layer perceptron model
@@ -21,61 +22,36 @@ pub struct MLP {
     layers: Vec<Layer>,
 }
 
-impl Neuron {
-    pub fn new(nin: usize) -> Self {
+impl Layer {
+    pub fn new(nin: usize, nout: usize) -> Self {
         assert!(nin > 0);
         let mut rng = rand::thread_rng();
-        let weights = Vec::from_iter((0..nin).map(|_| rng.gen_range(-1.0..=1.0).into()));
-        Self {
-            bias: rng.gen_range(-1.0..=1.0).into(),
-            weights,
-        }
+        let weights = Tensor::new(
+            (0..nin * nout).map(|_| rng.gen_range(-1.0..=1.0)).collect(),
+            (nin, nout),
+        );
+        let bias = Tensor::new(
+            (0..nout).map(|_| rng.gen_range(-1.0..=1.0)).collect(),
+            (1, nout),
+        );
+        Self { weights, bias }
     }
 
-    pub fn call(&self, input: &[Val]) -> Val {
-        assert_eq!(input.len(), self.weights.len());
-        input
-            .iter()
-            .zip(&self.weights)
-            .map(|(x, w)| x * w * &self.bias)
-            .reduce(|acc, v| acc + v)
-            .map(|v| v.tanh())
-            .unwrap()
+    pub fn call(&self, input: &Tensor) -> Tensor {
+        input.matmul(&self.weights).broadcast_add(&self.bias).tanh()
     }
 
-    pub fn parameters(&self) -> Vec<Val> {
-        let mut params = self.weights.clone();
-        params.push(self.bias.clone());
-        params
+    pub fn parameters(&self) -> Vec<Tensor> {
+        vec![self.weights.clone(), self.bias.clone()]
     }
 
     pub fn zero_grad(&self) {
-        self.weights.iter().for_each(|w| {
-            w.zero_grad();
-        });
+        self.weights.zero_grad();
         self.bias.zero_grad();
     }
-}
-
-impl Layer {
-    pub fn new(nin: usize, nout: usize) -> Self {
-        let neurons = Vec::from_iter((0..nout).map(|_| Neuron::new(nin)));
-        Self { neurons }
-    }
-
-    pub fn call(&self, input: &[Val]) -> Vec<Val> {
-        self.neurons.iter().map(|n| n.call(input)).collect()
-    }
 
-    pub fn parameters(&self) -> Vec<Val> {
-        self.neurons.iter().fold(vec![], |mut acc, neuron| {
-            acc.append(&mut neuron.parameters());
-            acc
-        })
-    }
-
-    pub fn zero_grad(&self) {
-        self.neurons.iter().for_each(|n| n.zero_grad());
+    pub fn shape(&self) -> (usize, usize) {
+        self.weights.shape()
     }
 }
 
@@ -86,16 +62,15 @@ impl MLP {
         Self { layers }
     }
 
-    /// Run the model with an input of values
-    pub fn call(&self, input: &[Val]) -> Vec<Val> {
-        let input = input.iter().map(|i| i.into()).collect::<Vec<_>>();
+    /// Run the model on a `(batch, nin)` input, returning a `(batch, nout)` tensor.
+    pub fn call(&self, input: &Tensor) -> Tensor {
         self.layers
             .iter()
-            .fold(input.to_owned(), |input, layer| layer.call(&input))
+            .fold(input.clone(), |input, layer| layer.call(&input))
     }
 
     /// Collect all the weights of this model
-    pub fn parameters(&self) -> Vec<Val> {
+    pub fn parameters(&self) -> Vec<Tensor> {
         self.layers.iter().fold(vec![], |mut acc, layer| {
             acc.append(&mut layer.parameters());
             acc
@@ -106,4 +81,58 @@ impl MLP {
     pub fn zero_grad(&self) {
         self.layers.iter().for_each(|l| l.zero_grad());
     }
+
+    /// The `[nin, nout_0, nout_1, ...]` sizes this [`MLP`] was built from,
+    /// i.e. what you'd pass back into [`MLP::new`] to reconstruct its shape.
+    pub fn layer_shapes(&self) -> Vec<usize> {
+        let mut sizes = vec![self.layers[0].shape().0];
+        sizes.extend(self.layers.iter().map(|l| l.shape().1));
+        sizes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_persistent_graph_training_loss_decreases() {
+        // Builds the graph once and drives it with reset_computation/forward
+        // across many epochs, like the walk_vs_stop example does. If
+        // intermediate nodes' grad isn't cleared each epoch, their gradients
+        // accumulate and the loss diverges instead of decreasing.
+        let mlp = MLP::new(&[2, 4, 1]);
+        let xs = Tensor::new(vec![1.0, 0.5, -1.0, 0.3, 0.2, -0.4], (3, 2));
+        let ys = Tensor::new(vec![1.0, -1.0, 0.0], (3, 1));
+
+        let pred = mlp.call(&xs);
+        let diff = &pred - &ys;
+        let loss = (&diff * &diff).sum();
+
+        loss.reset_computation();
+        loss.forward();
+        let first_loss = loss.data()[0];
+
+        for _ in 0..50 {
+            loss.reset_computation();
+            loss.forward();
+            mlp.zero_grad();
+            loss.backward();
+            mlp.parameters().iter().for_each(|p| {
+                let step: Vec<f32> = p.grad().iter().map(|g| -0.05 * g).collect();
+                p.add_data(&step);
+            });
+        }
+
+        loss.reset_computation();
+        loss.forward();
+        let last_loss = loss.data()[0];
+
+        assert!(
+            last_loss < first_loss,
+            "loss should decrease over training: {} -> {}",
+            first_loss,
+            last_loss
+        );
+    }
 }
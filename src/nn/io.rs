@@ -0,0 +1,134 @@
+use super::MLP;
+
+impl MLP {
+    /// Encodes this model's topology and weights as a compact little-endian
+    /// byte stream: a `u32` count of [`MLP::layer_shapes`] entries (one more
+    /// than the number of layers), that many `u64` layer sizes, then
+    /// every parameter's `f32` values in [`MLP::parameters`] order (weights
+    /// then bias, layer by layer).
+    pub fn save_bytes(&self) -> Vec<u8> {
+        let sizes = self.layer_shapes();
+        let mut bytes = Vec::new();
+        bytes.extend((sizes.len() as u32).to_le_bytes());
+        for size in &sizes {
+            bytes.extend((*size as u64).to_le_bytes());
+        }
+        for param in self.parameters() {
+            for value in param.data() {
+                bytes.extend(value.to_le_bytes());
+            }
+        }
+        bytes
+    }
+
+    /// Reconstructs an [`MLP`] of the shape and weights encoded by
+    /// [`MLP::save_bytes`].
+    pub fn load_bytes(bytes: &[u8]) -> Self {
+        let mut cursor = 0;
+        let num_sizes = read_u32(bytes, &mut cursor) as usize;
+        let sizes: Vec<usize> = (0..num_sizes)
+            .map(|_| read_u64(bytes, &mut cursor) as usize)
+            .collect();
+
+        let mlp = MLP::new(&sizes);
+        for param in mlp.parameters() {
+            let len = param.data().len();
+            let values = (0..len).map(|_| read_f32(bytes, &mut cursor)).collect();
+            param.set_data(values);
+        }
+        mlp
+    }
+
+    /// Snapshots this model's topology and weights for the serde-backed
+    /// round-trip path (pick your own serializer: JSON, bincode, ...).
+    pub fn to_weights(&self) -> MlpWeights {
+        MlpWeights {
+            sizes: self.layer_shapes(),
+            weights: self.parameters().iter().flat_map(|p| p.data()).collect(),
+        }
+    }
+
+    /// Reconstructs an [`MLP`] from a [`MlpWeights`] snapshot.
+    pub fn from_weights(weights: &MlpWeights) -> Self {
+        let mlp = MLP::new(&weights.sizes);
+        let mut cursor = 0;
+        for param in mlp.parameters() {
+            let len = param.data().len();
+            param.set_data(weights.weights[cursor..cursor + len].to_vec());
+            cursor += len;
+        }
+        mlp
+    }
+}
+
+fn read_u32(bytes: &[u8], cursor: &mut usize) -> u32 {
+    let value = u32::from_le_bytes(bytes[*cursor..*cursor + 4].try_into().unwrap());
+    *cursor += 4;
+    value
+}
+
+fn read_u64(bytes: &[u8], cursor: &mut usize) -> u64 {
+    let value = u64::from_le_bytes(bytes[*cursor..*cursor + 8].try_into().unwrap());
+    *cursor += 8;
+    value
+}
+
+fn read_f32(bytes: &[u8], cursor: &mut usize) -> f32 {
+    let value = f32::from_le_bytes(bytes[*cursor..*cursor + 4].try_into().unwrap());
+    *cursor += 4;
+    value
+}
+
+/// Plain-data snapshot of an [`MLP`]'s topology (see [`MLP::layer_shapes`])
+/// and weights (in [`MLP::parameters`] order), suitable for `serde`.
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct MlpWeights {
+    pub sizes: Vec<usize>,
+    pub weights: Vec<f32>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tensor::Tensor;
+
+    #[test]
+    fn test_save_and_load_bytes_roundtrip_predictions() {
+        let mlp = train_briefly();
+        let input = Tensor::new(vec![0.3, -0.2], (1, 2));
+        let before = mlp.call(&input).data();
+
+        let bytes = mlp.save_bytes();
+        let reloaded = MLP::load_bytes(&bytes);
+
+        assert_eq!(before, reloaded.call(&input).data());
+    }
+
+    #[test]
+    fn test_weights_snapshot_roundtrip_predictions() {
+        let mlp = train_briefly();
+        let input = Tensor::new(vec![0.3, -0.2], (1, 2));
+        let before = mlp.call(&input).data();
+
+        let reloaded = MLP::from_weights(&mlp.to_weights());
+
+        assert_eq!(before, reloaded.call(&input).data());
+    }
+
+    fn train_briefly() -> MLP {
+        let mlp = MLP::new(&[2, 3, 1]);
+        let input = Tensor::new(vec![0.3, -0.2], (1, 2));
+
+        for _ in 0..5 {
+            let pred = mlp.call(&input);
+            let loss = (&pred * &pred).sum();
+            mlp.zero_grad();
+            loss.backward();
+            mlp.parameters().iter().for_each(|p| {
+                let step: Vec<f32> = p.grad().iter().map(|g| -0.01 * g).collect();
+                p.add_data(&step);
+            });
+        }
+        mlp
+    }
+}
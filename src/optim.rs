@@ -0,0 +1,135 @@
+use crate::tensor::Tensor;
+
+/// Common interface for weight-update rules, so a training loop can swap
+/// between e.g. [`Sgd`] and [`Adam`] without changing anything but the
+/// optimizer it constructs.
+pub trait Optimizer {
+    /// Applies one update to `params` using their current `grad()`.
+    fn step(&mut self, params: &[Tensor]);
+
+    /// Resets every parameter's accumulated gradient to zero.
+    fn zero_grad(&self, params: &[Tensor]) {
+        params.iter().for_each(|p| p.zero_grad());
+    }
+}
+
+/// Plain SGD, with optional momentum. With `momentum` at `0.0` this is the
+/// `p.add_data(-lr * p.grad())` update the example used to do by hand.
+pub struct Sgd {
+    lr: f32,
+    momentum: f32,
+    velocity: Vec<Vec<f32>>,
+}
+
+impl Sgd {
+    pub fn new(lr: f32, momentum: f32) -> Self {
+        Self {
+            lr,
+            momentum,
+            velocity: Vec::new(),
+        }
+    }
+}
+
+impl Optimizer for Sgd {
+    fn step(&mut self, params: &[Tensor]) {
+        if self.velocity.is_empty() {
+            self.velocity = params.iter().map(|p| vec![0.0; p.data().len()]).collect();
+        }
+
+        for (param, velocity) in params.iter().zip(self.velocity.iter_mut()) {
+            let grad = param.grad();
+            let step: Vec<f32> = grad
+                .iter()
+                .zip(velocity.iter_mut())
+                .map(|(g, v)| {
+                    *v = self.momentum * *v + g;
+                    -self.lr * *v
+                })
+                .collect();
+            param.add_data(&step);
+        }
+    }
+}
+
+/// Adam, maintaining bias-corrected first and second moment estimates per
+/// parameter.
+pub struct Adam {
+    lr: f32,
+    beta1: f32,
+    beta2: f32,
+    eps: f32,
+    m: Vec<Vec<f32>>,
+    v: Vec<Vec<f32>>,
+    t: i32,
+}
+
+impl Adam {
+    pub fn new(lr: f32, beta1: f32, beta2: f32, eps: f32) -> Self {
+        Self {
+            lr,
+            beta1,
+            beta2,
+            eps,
+            m: Vec::new(),
+            v: Vec::new(),
+            t: 0,
+        }
+    }
+}
+
+impl Optimizer for Adam {
+    fn step(&mut self, params: &[Tensor]) {
+        if self.m.is_empty() {
+            self.m = params.iter().map(|p| vec![0.0; p.data().len()]).collect();
+            self.v = params.iter().map(|p| vec![0.0; p.data().len()]).collect();
+        }
+
+        self.t += 1;
+        let bias_correction1 = 1.0 - self.beta1.powi(self.t);
+        let bias_correction2 = 1.0 - self.beta2.powi(self.t);
+
+        for ((param, m), v) in params.iter().zip(self.m.iter_mut()).zip(self.v.iter_mut()) {
+            let grad = param.grad();
+            let mut step = vec![0.0; grad.len()];
+            for i in 0..grad.len() {
+                m[i] = self.beta1 * m[i] + (1.0 - self.beta1) * grad[i];
+                v[i] = self.beta2 * v[i] + (1.0 - self.beta2) * grad[i] * grad[i];
+                let m_hat = m[i] / bias_correction1;
+                let v_hat = v[i] / bias_correction2;
+                step[i] = -self.lr * m_hat / (v_hat.sqrt() + self.eps);
+            }
+            param.add_data(&step);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sgd_without_momentum_matches_plain_update() {
+        let param = Tensor::new(vec![1.0, 2.0], (1, 2));
+        param.add_data(&[0.0, 0.0]);
+        param.inner.as_ref().borrow_mut().grad = vec![0.5, -0.5];
+
+        let mut sgd = Sgd::new(0.1, 0.0);
+        sgd.step(std::slice::from_ref(&param));
+
+        assert_eq!(vec![0.95, 2.05], param.data());
+    }
+
+    #[test]
+    fn test_adam_first_step_moves_towards_negative_gradient() {
+        let param = Tensor::new(vec![0.0], (1, 1));
+        param.inner.as_ref().borrow_mut().grad = vec![1.0];
+
+        let mut adam = Adam::new(0.1, 0.9, 0.999, 1e-8);
+        adam.step(std::slice::from_ref(&param));
+
+        // m_hat = v_hat = 1.0 after bias correction on the first step, so the
+        // update is -lr / (1 + eps) ~= -lr.
+        assert!((param.data()[0] - (-0.1)).abs() < 1e-3);
+    }
+}
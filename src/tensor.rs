@@ -0,0 +1,282 @@
+use std::{
+    cell::RefCell,
+    ops::{Add, Mul, Sub},
+    rc::Rc,
+};
+
+use inner::TensorInner;
+
+mod inner;
+
+/// A 2-D batch of values with its own autodiff graph, mirroring [`crate::engine::Val`]
+/// but operating on a `Vec<f32>` data buffer plus `(rows, cols)` shape instead
+/// of a single scalar. Lets a layer do one matrix-vector product instead of a
+/// scalar loop per weight.
+#[derive(Debug, Clone)]
+pub struct Tensor {
+    pub inner: Rc<RefCell<TensorInner>>,
+}
+
+impl Tensor {
+    pub fn new(data: Vec<f32>, shape: (usize, usize)) -> Self {
+        Self {
+            inner: TensorInner::rc(data, shape, None),
+        }
+    }
+
+    pub fn zeros(shape: (usize, usize)) -> Self {
+        Self::new(vec![0.0; shape.0 * shape.1], shape)
+    }
+
+    pub fn shape(&self) -> (usize, usize) {
+        self.inner.as_ref().borrow().shape
+    }
+
+    pub fn data(&self) -> Vec<f32> {
+        self.inner.as_ref().borrow().data.clone()
+    }
+
+    pub fn grad(&self) -> Vec<f32> {
+        self.inner.as_ref().borrow().grad.clone()
+    }
+
+    pub fn add_data(&self, delta: &[f32]) {
+        let mut value = self.inner.as_ref().borrow_mut();
+        assert_eq!(delta.len(), value.data.len());
+        for (d, g) in value.data.iter_mut().zip(delta) {
+            *d += g;
+        }
+    }
+
+    /// Overwrites this tensor's `data` in place, e.g. to install weights
+    /// loaded from disk. The new data must match the existing shape.
+    pub fn set_data(&self, data: Vec<f32>) {
+        let mut value = self.inner.as_ref().borrow_mut();
+        assert_eq!(data.len(), value.data.len());
+        value.data = data;
+    }
+
+    pub fn zero_grad(&self) {
+        self.inner
+            .as_ref()
+            .borrow_mut()
+            .grad
+            .iter_mut()
+            .for_each(|g| *g = 0.0);
+    }
+
+    pub fn matmul(&self, rhs: &Tensor) -> Tensor {
+        Tensor {
+            inner: TensorInner::matmul(self.inner.clone(), rhs.inner.clone()),
+        }
+    }
+
+    pub fn broadcast_add(&self, bias: &Tensor) -> Tensor {
+        Tensor {
+            inner: TensorInner::broadcast_add(self.inner.clone(), bias.inner.clone()),
+        }
+    }
+
+    pub fn tanh(&self) -> Tensor {
+        Tensor {
+            inner: TensorInner::tanh(self.inner.clone()),
+        }
+    }
+
+    pub fn relu(&self) -> Tensor {
+        Tensor {
+            inner: TensorInner::relu(self.inner.clone()),
+        }
+    }
+
+    pub fn exp(&self) -> Tensor {
+        Tensor {
+            inner: TensorInner::exp(self.inner.clone()),
+        }
+    }
+
+    /// Reduces every element to a single `1x1` tensor, e.g. to turn an
+    /// elementwise loss into a scalar `backward()` can seed.
+    pub fn sum(&self) -> Tensor {
+        Tensor {
+            inner: TensorInner::sum(self.inner.clone()),
+        }
+    }
+
+    pub fn ln(&self) -> Tensor {
+        Tensor {
+            inner: TensorInner::ln(self.inner.clone()),
+        }
+    }
+
+    pub fn sigmoid(&self) -> Tensor {
+        Tensor {
+            inner: TensorInner::sigmoid(self.inner.clone()),
+        }
+    }
+
+    pub fn neg(&self) -> Tensor {
+        Tensor {
+            inner: TensorInner::neg(self.inner.clone()),
+        }
+    }
+
+    /// Numerically stable row-wise log-softmax, treating each row as one
+    /// example's logits. Mirrors [`crate::log_softmax`] but as a single
+    /// batched, differentiable op instead of a chain of scalar ones.
+    pub fn log_softmax(&self) -> Tensor {
+        Tensor {
+            inner: TensorInner::log_softmax(self.inner.clone()),
+        }
+    }
+
+    /// Cross-entropy loss given `self` holds log-probabilities (e.g. from
+    /// [`Tensor::log_softmax`]) and `target` is one-hot, summed over the
+    /// whole batch. Mirrors [`crate::cross_entropy`].
+    pub fn cross_entropy(&self, target: &Tensor) -> Tensor {
+        (self * target).sum().neg()
+    }
+
+    pub fn backward(&self) {
+        TensorInner::backward(&self.inner);
+    }
+
+    pub fn forward(&self) {
+        TensorInner::forward(&self.inner);
+    }
+
+    pub fn reset_computation(&self) {
+        TensorInner::reset_computation(&self.inner);
+    }
+}
+
+impl Add for &Tensor {
+    type Output = Tensor;
+
+    fn add(self, rhs: Self) -> Tensor {
+        Tensor {
+            inner: TensorInner::add(self.inner.clone(), rhs.inner.clone()),
+        }
+    }
+}
+
+impl Sub for &Tensor {
+    type Output = Tensor;
+
+    fn sub(self, rhs: Self) -> Tensor {
+        Tensor {
+            inner: TensorInner::sub(self.inner.clone(), rhs.inner.clone()),
+        }
+    }
+}
+
+impl Mul for &Tensor {
+    type Output = Tensor;
+
+    fn mul(self, rhs: Self) -> Tensor {
+        Tensor {
+            inner: TensorInner::mul(self.inner.clone(), rhs.inner.clone()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_matmul_backward() {
+        // A (2x2) . B (2x2) = C
+        let a = Tensor::new(vec![1.0, 2.0, 3.0, 4.0], (2, 2));
+        let b = Tensor::new(vec![5.0, 6.0, 7.0, 8.0], (2, 2));
+        let c = a.matmul(&b);
+
+        assert_eq!(vec![19.0, 22.0, 43.0, 50.0], c.data());
+
+        c.backward();
+        // dC/dA = ones(2,2) . B^T, dC/dB = A^T . ones(2,2)
+        assert_eq!(vec![11.0, 15.0, 11.0, 15.0], a.grad());
+        assert_eq!(vec![4.0, 4.0, 6.0, 6.0], b.grad());
+    }
+
+    #[test]
+    fn test_broadcast_add_backward() {
+        let left = Tensor::new(vec![1.0, 2.0, 3.0, 4.0], (2, 2));
+        let bias = Tensor::new(vec![10.0, 20.0], (1, 2));
+        let out = left.broadcast_add(&bias);
+
+        assert_eq!(vec![11.0, 22.0, 13.0, 24.0], out.data());
+
+        out.backward();
+        assert_eq!(vec![1.0, 1.0, 1.0, 1.0], left.grad());
+        assert_eq!(vec![2.0, 2.0], bias.grad());
+    }
+
+    #[test]
+    fn test_elementwise_tanh_backward() {
+        let x = Tensor::new(vec![0.0, 0.0], (1, 2));
+        let y = x.tanh();
+
+        assert_eq!(vec![0.0, 0.0], y.data());
+
+        y.backward();
+        assert_eq!(vec![1.0, 1.0], x.grad());
+    }
+
+    #[test]
+    fn test_forward_recomputes_after_leaf_mutation() {
+        let a = Tensor::new(vec![1.0, 2.0], (1, 2));
+        let b = Tensor::new(vec![3.0, 4.0], (1, 2));
+        let c = &a + &b;
+
+        assert_eq!(vec![4.0, 6.0], c.data());
+
+        a.add_data(&[1.0, 1.0]);
+        c.reset_computation();
+        c.forward();
+
+        assert_eq!(vec![5.0, 7.0], c.data());
+    }
+
+    #[test]
+    fn test_sigmoid_backward() {
+        let x = Tensor::new(vec![0.0, 0.0], (1, 2));
+        let y = x.sigmoid();
+
+        assert_eq!(vec![0.5, 0.5], y.data());
+
+        y.backward();
+        assert_eq!(vec![0.25, 0.25], x.grad());
+    }
+
+    #[test]
+    fn test_ln_backward() {
+        let x = Tensor::new(vec![2.0, 4.0], (1, 2));
+        let y = x.ln();
+
+        y.backward();
+        assert_eq!(vec![0.5, 0.25], x.grad());
+    }
+
+    #[test]
+    fn test_log_softmax_rows_sum_to_one() {
+        let x = Tensor::new(vec![1.0, 2.0, 3.0, 0.0, 0.0, 0.0], (2, 3));
+        let log_probs = x.log_softmax();
+
+        for row in log_probs.data().chunks(3) {
+            let sum: f32 = row.iter().map(|p| p.exp()).sum();
+            assert!((sum - 1.0).abs() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn test_cross_entropy_picks_out_target_class() {
+        let logits = Tensor::new(vec![2.0, 0.5, 0.1], (1, 3));
+        let target = Tensor::new(vec![1.0, 0.0, 0.0], (1, 3));
+        let log_probs = logits.log_softmax();
+
+        let loss = log_probs.cross_entropy(&target);
+
+        assert_eq!(-log_probs.data()[0], loss.data()[0]);
+    }
+}
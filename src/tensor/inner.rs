@@ -0,0 +1,584 @@
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashSet,
+    rc::Rc,
+};
+
+#[derive(Debug, PartialEq)]
+pub struct TensorInner {
+    pub data: Vec<f32>,
+    pub grad: Vec<f32>,
+    pub shape: (usize, usize),
+    pub op: Option<Op>,
+    computed: Cell<bool>,
+}
+
+impl TensorInner {
+    fn new(data: Vec<f32>, shape: (usize, usize)) -> Self {
+        assert_eq!(
+            data.len(),
+            shape.0 * shape.1,
+            "data length does not match shape {:?}",
+            shape
+        );
+        let grad = vec![0.0; data.len()];
+        Self {
+            data,
+            grad,
+            shape,
+            op: None,
+            computed: Cell::new(true),
+        }
+    }
+
+    pub fn rc(data: Vec<f32>, shape: (usize, usize), op: Option<Op>) -> Rc<RefCell<Self>> {
+        let mut inner = Self::new(data, shape);
+        inner.op = op;
+        Rc::new(RefCell::new(inner))
+    }
+
+    pub fn add(left: Rc<RefCell<Self>>, right: Rc<RefCell<Self>>) -> Rc<RefCell<Self>> {
+        let (data, shape) = {
+            let l = left.as_ref().borrow();
+            let r = right.as_ref().borrow();
+            assert_eq!(l.shape, r.shape, "elementwise add requires matching shapes");
+            let data = l.data.iter().zip(&r.data).map(|(a, b)| a + b).collect();
+            (data, l.shape)
+        };
+        Self::rc(data, shape, Some(Op::Add { left, right }))
+    }
+
+    pub fn neg(value: Rc<RefCell<Self>>) -> Rc<RefCell<Self>> {
+        let shape = value.as_ref().borrow().shape;
+        let neg_one = Self::rc(vec![-1.0; shape.0 * shape.1], shape, None);
+        Self::mul(value, neg_one)
+    }
+
+    pub fn sub(left: Rc<RefCell<Self>>, right: Rc<RefCell<Self>>) -> Rc<RefCell<Self>> {
+        Self::add(left, Self::neg(right))
+    }
+
+    pub fn mul(left: Rc<RefCell<Self>>, right: Rc<RefCell<Self>>) -> Rc<RefCell<Self>> {
+        let (data, shape) = {
+            let l = left.as_ref().borrow();
+            let r = right.as_ref().borrow();
+            assert_eq!(l.shape, r.shape, "elementwise mul requires matching shapes");
+            let data = l.data.iter().zip(&r.data).map(|(a, b)| a * b).collect();
+            (data, l.shape)
+        };
+        Self::rc(data, shape, Some(Op::Mul { left, right }))
+    }
+
+    pub fn matmul(left: Rc<RefCell<Self>>, right: Rc<RefCell<Self>>) -> Rc<RefCell<Self>> {
+        let (data, shape) = {
+            let l = left.as_ref().borrow();
+            let r = right.as_ref().borrow();
+            assert_eq!(
+                l.shape.1, r.shape.0,
+                "matmul shape mismatch: {:?} x {:?}",
+                l.shape, r.shape
+            );
+            let shape = (l.shape.0, r.shape.1);
+            (matmul(&l.data, l.shape, &r.data, r.shape), shape)
+        };
+        Self::rc(data, shape, Some(Op::Matmul { left, right }))
+    }
+
+    pub fn broadcast_add(left: Rc<RefCell<Self>>, bias: Rc<RefCell<Self>>) -> Rc<RefCell<Self>> {
+        let (data, shape) = {
+            let l = left.as_ref().borrow();
+            let b = bias.as_ref().borrow();
+            let (m, n) = l.shape;
+            assert_eq!(
+                b.shape,
+                (1, n),
+                "broadcast_add bias must have shape (1, {}), got {:?}",
+                n,
+                b.shape
+            );
+            let mut data = vec![0.0; m * n];
+            for i in 0..m {
+                for j in 0..n {
+                    data[i * n + j] = l.data[i * n + j] + b.data[j];
+                }
+            }
+            (data, l.shape)
+        };
+        Self::rc(data, shape, Some(Op::BroadcastAdd { left, bias }))
+    }
+
+    pub fn tanh(value: Rc<RefCell<Self>>) -> Rc<RefCell<Self>> {
+        let (tanh, shape) = {
+            let v = value.as_ref().borrow();
+            (v.data.iter().map(|x| x.tanh()).collect::<Vec<_>>(), v.shape)
+        };
+        Self::rc(tanh.clone(), shape, Some(Op::Tanh { val: value, tanh }))
+    }
+
+    pub fn relu(value: Rc<RefCell<Self>>) -> Rc<RefCell<Self>> {
+        let (relu, shape) = {
+            let v = value.as_ref().borrow();
+            (
+                v.data.iter().map(|x| x.max(0.0)).collect::<Vec<_>>(),
+                v.shape,
+            )
+        };
+        Self::rc(relu.clone(), shape, Some(Op::Relu { val: value, relu }))
+    }
+
+    pub fn exp(value: Rc<RefCell<Self>>) -> Rc<RefCell<Self>> {
+        let (exp, shape) = {
+            let v = value.as_ref().borrow();
+            (v.data.iter().map(|x| x.exp()).collect::<Vec<_>>(), v.shape)
+        };
+        Self::rc(exp.clone(), shape, Some(Op::Exp { val: value, exp }))
+    }
+
+    pub fn sum(value: Rc<RefCell<Self>>) -> Rc<RefCell<Self>> {
+        let data = vec![value.as_ref().borrow().data.iter().sum()];
+        Self::rc(data, (1, 1), Some(Op::Sum { val: value }))
+    }
+
+    pub fn ln(value: Rc<RefCell<Self>>) -> Rc<RefCell<Self>> {
+        let (ln, shape) = {
+            let v = value.as_ref().borrow();
+            (v.data.iter().map(|x| x.ln()).collect::<Vec<_>>(), v.shape)
+        };
+        Self::rc(ln, shape, Some(Op::Ln { val: value }))
+    }
+
+    pub fn sigmoid(value: Rc<RefCell<Self>>) -> Rc<RefCell<Self>> {
+        let (sigmoid, shape) = {
+            let v = value.as_ref().borrow();
+            (
+                v.data
+                    .iter()
+                    .map(|x| 1.0 / (1.0 + (-x).exp()))
+                    .collect::<Vec<_>>(),
+                v.shape,
+            )
+        };
+        Self::rc(
+            sigmoid.clone(),
+            shape,
+            Some(Op::Sigmoid {
+                val: value,
+                sigmoid,
+            }),
+        )
+    }
+
+    /// Numerically stable row-wise log-softmax: each row of `value` (treated
+    /// as one example's logits) is shifted by its own max before
+    /// exponentiating, mirroring [`crate::log_softmax`] but batched and
+    /// differentiable as a single op instead of a chain of scalar ones.
+    pub fn log_softmax(value: Rc<RefCell<Self>>) -> Rc<RefCell<Self>> {
+        let (log_probs, shape, softmax) = {
+            let v = value.as_ref().borrow();
+            let (log_probs, softmax) = log_softmax_rows(&v.data, v.shape);
+            (log_probs, v.shape, softmax)
+        };
+        Self::rc(
+            log_probs,
+            shape,
+            Some(Op::LogSoftmax { val: value, softmax }),
+        )
+    }
+
+    /// Runs a full reverse-mode pass starting at `root`, mirroring
+    /// [`crate::engine::inner::ValInner::backward`] but over vectors of
+    /// gradients instead of a single scalar per node.
+    pub fn backward(root: &Rc<RefCell<Self>>) {
+        let mut visited = HashSet::new();
+        let mut topo = Vec::new();
+        Self::build_topo(root, &mut visited, &mut topo);
+
+        {
+            let mut r = root.as_ref().borrow_mut();
+            r.grad = vec![1.0; r.data.len()];
+        }
+
+        for node in topo.into_iter().rev() {
+            let mut value = node.as_ref().borrow_mut();
+            let grad = value.grad.clone();
+            if let Some(op) = value.op.as_mut() {
+                op.backward(&grad);
+            }
+        }
+    }
+
+    fn build_topo(
+        node: &Rc<RefCell<Self>>,
+        visited: &mut HashSet<usize>,
+        topo: &mut Vec<Rc<RefCell<Self>>>,
+    ) {
+        if !visited.insert(Rc::as_ptr(node) as *const _ as usize) {
+            return;
+        }
+        if let Some(op) = &node.as_ref().borrow().op {
+            for child in op.children() {
+                Self::build_topo(&child, visited, topo);
+            }
+        }
+        topo.push(Rc::clone(node));
+    }
+
+    /// Recomputes `data`/`shape` for `node` from its `Op` inputs, bottom-up,
+    /// short-circuiting on already-computed nodes. Mirrors
+    /// [`crate::engine::inner::ValInner::forward`].
+    pub fn forward(node: &Rc<RefCell<Self>>) {
+        if node.as_ref().borrow().computed.get() {
+            return;
+        }
+
+        let children = node
+            .as_ref()
+            .borrow()
+            .op
+            .as_ref()
+            .map(Op::children)
+            .unwrap_or_default();
+        for child in &children {
+            Self::forward(child);
+        }
+
+        let mut value = node.as_ref().borrow_mut();
+        if let Some(op) = value.op.as_mut() {
+            let (data, shape) = op.forward();
+            value.data = data;
+            value.shape = shape;
+        }
+        value.computed.set(true);
+    }
+
+    /// Marks `node` and everything it depends on as not-yet-computed and
+    /// zeroes its accumulated `grad`, so gradients from a previous epoch
+    /// don't keep piling onto intermediate nodes that nothing else zeroes.
+    /// Mirrors [`crate::engine::inner::ValInner::reset_computation`].
+    pub fn reset_computation(node: &Rc<RefCell<Self>>) {
+        let mut value = node.as_ref().borrow_mut();
+        if !value.computed.get() {
+            return;
+        }
+        value.computed.set(false);
+        value.grad.iter_mut().for_each(|g| *g = 0.0);
+
+        let children = value.op.as_ref().map(Op::children).unwrap_or_default();
+        drop(value);
+        for child in &children {
+            Self::reset_computation(child);
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Op {
+    Add {
+        left: Rc<RefCell<TensorInner>>,
+        right: Rc<RefCell<TensorInner>>,
+    },
+    Mul {
+        left: Rc<RefCell<TensorInner>>,
+        right: Rc<RefCell<TensorInner>>,
+    },
+    Matmul {
+        left: Rc<RefCell<TensorInner>>,
+        right: Rc<RefCell<TensorInner>>,
+    },
+    BroadcastAdd {
+        left: Rc<RefCell<TensorInner>>,
+        bias: Rc<RefCell<TensorInner>>,
+    },
+    Tanh {
+        val: Rc<RefCell<TensorInner>>,
+        tanh: Vec<f32>,
+    },
+    Relu {
+        val: Rc<RefCell<TensorInner>>,
+        relu: Vec<f32>,
+    },
+    Exp {
+        val: Rc<RefCell<TensorInner>>,
+        exp: Vec<f32>,
+    },
+    Sum {
+        val: Rc<RefCell<TensorInner>>,
+    },
+    Ln {
+        val: Rc<RefCell<TensorInner>>,
+    },
+    Sigmoid {
+        val: Rc<RefCell<TensorInner>>,
+        sigmoid: Vec<f32>,
+    },
+    LogSoftmax {
+        val: Rc<RefCell<TensorInner>>,
+        softmax: Vec<f32>,
+    },
+}
+
+impl Op {
+    /// Adds this op's contribution of `grad` into its immediate children
+    /// only; callers must visit nodes in reverse topological order.
+    pub fn backward(&mut self, grad: &[f32]) {
+        match self {
+            Op::Add { left, right } => {
+                if Rc::ptr_eq(left, right) {
+                    let mut value = left.as_ref().borrow_mut();
+                    for (g, out) in value.grad.iter_mut().zip(grad) {
+                        *g += 2.0 * out;
+                    }
+                } else {
+                    add_into(left, grad);
+                    add_into(right, grad);
+                }
+            }
+            Op::Mul { left, right } => {
+                if Rc::ptr_eq(left, right) {
+                    let mut value = left.as_ref().borrow_mut();
+                    let data = value.data.clone();
+                    for ((g, d), out) in value.grad.iter_mut().zip(&data).zip(grad) {
+                        *g += 2.0 * d * out;
+                    }
+                } else {
+                    let left_data = left.as_ref().borrow().data.clone();
+                    let right_data = right.as_ref().borrow().data.clone();
+                    accumulate_scaled(left, &right_data, grad);
+                    accumulate_scaled(right, &left_data, grad);
+                }
+            }
+            Op::Matmul { left, right } => {
+                let a_shape = left.as_ref().borrow().shape;
+                let b_shape = right.as_ref().borrow().shape;
+                let out_shape = (a_shape.0, b_shape.1);
+                let a_data = left.as_ref().borrow().data.clone();
+                let b_data = right.as_ref().borrow().data.clone();
+
+                let (b_t, b_t_shape) = transpose(&b_data, b_shape);
+                let grad_a = matmul(grad, out_shape, &b_t, b_t_shape);
+                let (a_t, a_t_shape) = transpose(&a_data, a_shape);
+                let grad_b = matmul(&a_t, a_t_shape, grad, out_shape);
+
+                if Rc::ptr_eq(left, right) {
+                    let mut value = left.as_ref().borrow_mut();
+                    for (g, d) in value.grad.iter_mut().zip(&grad_a) {
+                        *g += d;
+                    }
+                    for (g, d) in value.grad.iter_mut().zip(&grad_b) {
+                        *g += d;
+                    }
+                } else {
+                    add_into(left, &grad_a);
+                    add_into(right, &grad_b);
+                }
+            }
+            Op::BroadcastAdd { left, bias } => {
+                add_into(left, grad);
+
+                let n = bias.as_ref().borrow().shape.1;
+                let m = grad.len() / n;
+                let mut bias_grad = vec![0.0; n];
+                for i in 0..m {
+                    for j in 0..n {
+                        bias_grad[j] += grad[i * n + j];
+                    }
+                }
+                add_into(bias, &bias_grad);
+            }
+            Op::Tanh { val, tanh } => {
+                let mut value = val.as_ref().borrow_mut();
+                for ((g, t), out) in value.grad.iter_mut().zip(tanh.iter()).zip(grad) {
+                    *g += (1.0 - t.powi(2)) * out;
+                }
+            }
+            Op::Relu { val, relu } => {
+                let mut value = val.as_ref().borrow_mut();
+                for ((g, r), out) in value.grad.iter_mut().zip(relu.iter()).zip(grad) {
+                    *g += if *r > 0.0 { *out } else { 0.0 };
+                }
+            }
+            Op::Exp { val, exp } => {
+                let mut value = val.as_ref().borrow_mut();
+                for ((g, e), out) in value.grad.iter_mut().zip(exp.iter()).zip(grad) {
+                    *g += e * out;
+                }
+            }
+            Op::Sum { val } => {
+                let len = val.as_ref().borrow().grad.len();
+                add_into(val, &vec![grad[0]; len]);
+            }
+            Op::Ln { val } => {
+                let mut value = val.as_ref().borrow_mut();
+                let data = value.data.clone();
+                for ((g, d), out) in value.grad.iter_mut().zip(&data).zip(grad) {
+                    *g += out / d;
+                }
+            }
+            Op::Sigmoid { val, sigmoid } => {
+                let mut value = val.as_ref().borrow_mut();
+                for ((g, s), out) in value.grad.iter_mut().zip(sigmoid.iter()).zip(grad) {
+                    *g += s * (1.0 - s) * out;
+                }
+            }
+            Op::LogSoftmax { val, softmax } => {
+                let (m, n) = val.as_ref().borrow().shape;
+                let mut grad_x = vec![0.0; m * n];
+                for i in 0..m {
+                    let row_sum: f32 = grad[i * n..(i + 1) * n].iter().sum();
+                    for j in 0..n {
+                        grad_x[i * n + j] = grad[i * n + j] - softmax[i * n + j] * row_sum;
+                    }
+                }
+                add_into(val, &grad_x);
+            }
+        }
+    }
+
+    /// Recomputes this op's `(data, shape)` from its children's current
+    /// `data`, refreshing cached derivative terms along the way.
+    fn forward(&mut self) -> (Vec<f32>, (usize, usize)) {
+        match self {
+            Op::Add { left, right } => {
+                let l = left.as_ref().borrow();
+                let r = right.as_ref().borrow();
+                (l.data.iter().zip(&r.data).map(|(a, b)| a + b).collect(), l.shape)
+            }
+            Op::Mul { left, right } => {
+                let l = left.as_ref().borrow();
+                let r = right.as_ref().borrow();
+                (l.data.iter().zip(&r.data).map(|(a, b)| a * b).collect(), l.shape)
+            }
+            Op::Matmul { left, right } => {
+                let l = left.as_ref().borrow();
+                let r = right.as_ref().borrow();
+                let shape = (l.shape.0, r.shape.1);
+                (matmul(&l.data, l.shape, &r.data, r.shape), shape)
+            }
+            Op::BroadcastAdd { left, bias } => {
+                let l = left.as_ref().borrow();
+                let b = bias.as_ref().borrow();
+                let (m, n) = l.shape;
+                let mut data = vec![0.0; m * n];
+                for i in 0..m {
+                    for j in 0..n {
+                        data[i * n + j] = l.data[i * n + j] + b.data[j];
+                    }
+                }
+                (data, l.shape)
+            }
+            Op::Tanh { val, tanh } => {
+                let v = val.as_ref().borrow();
+                *tanh = v.data.iter().map(|x| x.tanh()).collect();
+                (tanh.clone(), v.shape)
+            }
+            Op::Relu { val, relu } => {
+                let v = val.as_ref().borrow();
+                *relu = v.data.iter().map(|x| x.max(0.0)).collect();
+                (relu.clone(), v.shape)
+            }
+            Op::Exp { val, exp } => {
+                let v = val.as_ref().borrow();
+                *exp = v.data.iter().map(|x| x.exp()).collect();
+                (exp.clone(), v.shape)
+            }
+            Op::Sum { val } => (vec![val.as_ref().borrow().data.iter().sum()], (1, 1)),
+            Op::Ln { val } => {
+                let v = val.as_ref().borrow();
+                (v.data.iter().map(|x| x.ln()).collect(), v.shape)
+            }
+            Op::Sigmoid { val, sigmoid } => {
+                let v = val.as_ref().borrow();
+                *sigmoid = v.data.iter().map(|x| 1.0 / (1.0 + (-x).exp())).collect();
+                (sigmoid.clone(), v.shape)
+            }
+            Op::LogSoftmax { val, softmax } => {
+                let v = val.as_ref().borrow();
+                let (log_probs, new_softmax) = log_softmax_rows(&v.data, v.shape);
+                *softmax = new_softmax;
+                (log_probs, v.shape)
+            }
+        }
+    }
+
+    fn children(&self) -> Vec<Rc<RefCell<TensorInner>>> {
+        match self {
+            Op::Add { left, right } => vec![Rc::clone(left), Rc::clone(right)],
+            Op::Mul { left, right } => vec![Rc::clone(left), Rc::clone(right)],
+            Op::Matmul { left, right } => vec![Rc::clone(left), Rc::clone(right)],
+            Op::BroadcastAdd { left, bias } => vec![Rc::clone(left), Rc::clone(bias)],
+            Op::Tanh { val, .. } => vec![Rc::clone(val)],
+            Op::Relu { val, .. } => vec![Rc::clone(val)],
+            Op::Exp { val, .. } => vec![Rc::clone(val)],
+            Op::Sum { val } => vec![Rc::clone(val)],
+            Op::Ln { val } => vec![Rc::clone(val)],
+            Op::Sigmoid { val, .. } => vec![Rc::clone(val)],
+            Op::LogSoftmax { val, .. } => vec![Rc::clone(val)],
+        }
+    }
+}
+
+fn accumulate_scaled(node: &Rc<RefCell<TensorInner>>, scale: &[f32], grad: &[f32]) {
+    let mut value = node.as_ref().borrow_mut();
+    for ((g, s), out) in value.grad.iter_mut().zip(scale).zip(grad) {
+        *g += s * out;
+    }
+}
+
+fn add_into(node: &Rc<RefCell<TensorInner>>, grad: &[f32]) {
+    let mut value = node.as_ref().borrow_mut();
+    for (g, out) in value.grad.iter_mut().zip(grad) {
+        *g += out;
+    }
+}
+
+/// Row-major `a (m x k) · b (k x n) = out (m x n)`.
+fn matmul(a: &[f32], a_shape: (usize, usize), b: &[f32], b_shape: (usize, usize)) -> Vec<f32> {
+    let (m, k) = a_shape;
+    let (k2, n) = b_shape;
+    assert_eq!(k, k2, "matmul shape mismatch: {:?} x {:?}", a_shape, b_shape);
+
+    let mut out = vec![0.0; m * n];
+    for i in 0..m {
+        for p in 0..k {
+            let a_ip = a[i * k + p];
+            if a_ip == 0.0 {
+                continue;
+            }
+            for j in 0..n {
+                out[i * n + j] += a_ip * b[p * n + j];
+            }
+        }
+    }
+    out
+}
+
+/// Row-wise log-softmax plus the softmax itself (needed by the backward
+/// pass), both shaped like `a`.
+fn log_softmax_rows(a: &[f32], shape: (usize, usize)) -> (Vec<f32>, Vec<f32>) {
+    let (m, n) = shape;
+    let mut log_probs = vec![0.0; m * n];
+    let mut softmax = vec![0.0; m * n];
+    for i in 0..m {
+        let row = &a[i * n..(i + 1) * n];
+        let max = row.iter().cloned().fold(f32::NEG_INFINITY, f32::max);
+        let exp_shifted: Vec<f32> = row.iter().map(|x| (x - max).exp()).collect();
+        let sum_exp: f32 = exp_shifted.iter().sum();
+        let log_sum_exp = sum_exp.ln();
+        for j in 0..n {
+            log_probs[i * n + j] = (row[j] - max) - log_sum_exp;
+            softmax[i * n + j] = exp_shifted[j] / sum_exp;
+        }
+    }
+    (log_probs, softmax)
+}
+
+fn transpose(a: &[f32], shape: (usize, usize)) -> (Vec<f32>, (usize, usize)) {
+    let (m, n) = shape;
+    let mut out = vec![0.0; m * n];
+    for i in 0..m {
+        for j in 0..n {
+            out[j * m + i] = a[i * n + j];
+        }
+    }
+    (out, (n, m))
+}